@@ -3,6 +3,7 @@
  * For now this only wires the typed modules together; actual command handling comes later.
  */
 import { Command, InvalidArgumentError, Option } from "commander"
+import { isIP } from "net"
 import path from "path"
 import { fileURLToPath } from "url"
 import { createRequire } from "module"
@@ -108,8 +109,9 @@ function parsePort(input: string): number {
 }
 
 function resolveHost(input: string | undefined): string {
-  if (input && input.trim() === "0.0.0.0") {
-    return "0.0.0.0"
+  const trimmed = input?.trim()
+  if (trimmed && isIP(trimmed) !== 0) {
+    return trimmed
   }
   return DEFAULT_HOST
 }
@@ -0,0 +1,103 @@
+use crate::cli_manager::resolve_config_path;
+use log::{info, warn};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigChange {
+    pub path: String,
+    pub changed: Vec<String>,
+}
+
+/// Keeps the underlying file watcher alive; dropping it stops watching.
+pub struct ConfigWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+/// Watches the resolved config file and calls `on_change` with the
+/// `preferences` keys whose values changed.
+///
+/// The parent directory is watched rather than the file itself so editors that
+/// write a temp file and rename it over the original are still picked up.
+pub fn watch<F>(on_change: F) -> Option<ConfigWatcher>
+where
+    F: Fn(ConfigChange) + Send + 'static,
+{
+    let path = resolve_config_path();
+    let dir = path.parent()?.to_path_buf();
+    let file_name = path.file_name()?.to_os_string();
+    let mut last = read_preferences(&path).unwrap_or_default();
+
+    let watched = path.clone();
+    let mut watcher = match notify::recommended_watcher(move |res: notify::Result<Event>| {
+        let Ok(event) = res else {
+            return;
+        };
+        if !event
+            .paths
+            .iter()
+            .any(|p| p.file_name() == Some(file_name.as_os_str()))
+        {
+            return;
+        }
+        // A half-written file fails to parse; wait for the next event.
+        let Some(current) = read_preferences(&watched) else {
+            return;
+        };
+        let changed = changed_keys(&last, &current);
+        last = current;
+        if changed.is_empty() {
+            return;
+        }
+        info!("config changed: {changed:?}");
+        on_change(ConfigChange {
+            path: watched.to_string_lossy().to_string(),
+            changed,
+        });
+    }) {
+        Ok(watcher) => watcher,
+        Err(err) => {
+            warn!("failed to create config watcher: {err}");
+            return None;
+        }
+    };
+
+    if let Err(err) = watcher.watch(&dir, RecursiveMode::NonRecursive) {
+        warn!("failed to watch {}: {err}", dir.display());
+        return None;
+    }
+    info!("watching {}", path.display());
+    Some(ConfigWatcher { _watcher: watcher })
+}
+
+/// Returns `None` when the file exists but can't be read or parsed; a missing
+/// file counts as empty preferences.
+fn read_preferences(path: &Path) -> Option<Map<String, Value>> {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str::<Value>(&content).ok().map(|value| {
+            value
+                .get("preferences")
+                .and_then(Value::as_object)
+                .cloned()
+                .unwrap_or_default()
+        }),
+        Err(err) if err.kind() == ErrorKind::NotFound => Some(Map::new()),
+        Err(_) => None,
+    }
+}
+
+fn changed_keys(before: &Map<String, Value>, after: &Map<String, Value>) -> Vec<String> {
+    let mut keys: Vec<String> = before
+        .keys()
+        .chain(after.keys())
+        .filter(|key| before.get(*key) != after.get(*key))
+        .cloned()
+        .collect();
+    keys.sort();
+    keys.dedup();
+    keys
+}
@@ -0,0 +1,16 @@
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
+
+/// Shows a native message dialog, over the main window when it is visible.
+/// The main window can be missing (startup, other windows only) or hidden
+/// (closed on macOS, tray), so the dialog is shown on its own then.
+pub fn show(app: &AppHandle, title: &str, message: impl Into<String>, kind: MessageDialogKind) {
+    let mut dialog = app.dialog().message(message).title(title).kind(kind);
+    if let Some(window) = app
+        .get_webview_window("main")
+        .filter(|window| window.is_visible().unwrap_or(false))
+    {
+        dialog = dialog.parent(&window);
+    }
+    dialog.show(|_| {});
+}
@@ -0,0 +1,219 @@
+use chrono::{Local, SecondsFormat};
+use log::{Log, Metadata, Record};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+const MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+const KEEP_ROTATED: usize = 3;
+/// Upper bound on a single `read_chunk`, whatever the caller asks for.
+const MAX_CHUNK_BYTES: u64 = 1024 * 1024;
+
+/// A slice of the log file read by `read_chunk`.
+#[derive(Debug, Clone, Serialize)]
+pub struct LogChunk {
+    pub data: String,
+    /// Where the next read should start.
+    pub offset: u64,
+    /// Set when the requested offset was past the end of the file, meaning it
+    /// was rotated or truncated, so reading restarted at 0.
+    pub reset: bool,
+    /// Whether `offset` is at the end of the file.
+    pub eof: bool,
+}
+
+struct LogFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+static LOG_FILE: Lazy<Mutex<Option<LogFile>>> = Lazy::new(|| Mutex::new(None));
+
+/// Target of the lines forwarded from the CLI's stdout and stderr, so
+/// `RUST_LOG` can filter them apart from the app's own messages.
+pub const CLI_OUTPUT_TARGET: &str = "cli";
+
+// Printed only: the log file is what failed, and going through the logger
+// would append to it again.
+fn log_line(message: &str) {
+    eprintln!("[tauri-log] {message}");
+}
+
+/// Writes records to stderr through env_logger and appends the same records
+/// to the log file.
+struct AppLogger {
+    stderr: env_logger::Logger,
+}
+
+impl Log for AppLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.stderr.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.stderr.matches(record) {
+            return;
+        }
+        self.stderr.log(record);
+        append(&format!(
+            "{} [{}] {}",
+            record.level(),
+            record.target(),
+            record.args()
+        ));
+    }
+
+    fn flush(&self) {
+        self.stderr.flush();
+    }
+}
+
+/// Installs the logger, filtered by `RUST_LOG` (`info` when unset), e.g.
+/// `RUST_LOG=info,cli=off` hides the forwarded CLI output.
+pub fn init() {
+    let stderr =
+        env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).build();
+    let max_level = stderr.filter();
+    if log::set_boxed_logger(Box::new(AppLogger { stderr })).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
+/// Starts appending to `path`, or stops writing a log file when `None`.
+/// Missing parent directories are created.
+pub fn configure(path: Option<PathBuf>) {
+    let mut current = LOG_FILE.lock();
+    if current.as_ref().map(|log| &log.path) == path.as_ref() {
+        return;
+    }
+    *current = path.and_then(|path| match open(&path) {
+        Ok(log) => Some(log),
+        Err(err) => {
+            log_line(&format!(
+                "failed to open log file {}: {err}",
+                path.display()
+            ));
+            None
+        }
+    });
+}
+
+/// The log file being written, if any.
+pub fn path() -> Option<PathBuf> {
+    LOG_FILE.lock().as_ref().map(|log| log.path.clone())
+}
+
+/// Appends a timestamped line, rotating the file once it passes
+/// `MAX_FILE_BYTES`. Does nothing when no log file is configured.
+pub fn append(line: &str) {
+    let mut current = LOG_FILE.lock();
+    let Some(log) = current.as_mut() else {
+        return;
+    };
+    if log.size >= MAX_FILE_BYTES {
+        match rotate(&log.path) {
+            Ok(rotated) => *log = rotated,
+            Err(err) => {
+                log_line(&format!("failed to rotate {}: {err}", log.path.display()));
+                *current = None;
+                return;
+            }
+        }
+    }
+    let timestamp = Local::now().to_rfc3339_opts(SecondsFormat::Millis, false);
+    let entry = format!("{timestamp} {line}\n");
+    if log.file.write_all(entry.as_bytes()).is_ok() {
+        log.size += entry.len() as u64;
+    }
+}
+
+/// Reads up to `max_bytes` of the log file from `offset`, for viewers that
+/// tail it incrementally. A chunk never ends inside a UTF-8 character; the
+/// returned offset stops before it instead, unless that would leave the
+/// chunk empty, in which case the whole character is read past `max_bytes`.
+pub fn read_chunk(offset: u64, max_bytes: u64) -> Result<LogChunk, String> {
+    let path = path().ok_or_else(|| "no log file is configured".to_string())?;
+    let mut file =
+        File::open(&path).map_err(|err| format!("failed to open {}: {err}", path.display()))?;
+    let len = file.metadata().map_err(|err| err.to_string())?.len();
+    let reset = offset > len;
+    let start = if reset { 0 } else { offset };
+    let want = max_bytes.min(MAX_CHUNK_BYTES).min(len - start);
+    // One character's worth more, for when `want` ends inside the first one.
+    let extra = (len - start - want).min(4);
+    file.seek(SeekFrom::Start(start))
+        .map_err(|err| err.to_string())?;
+    let mut bytes = Vec::with_capacity((want + extra) as usize);
+    file.take(want + extra)
+        .read_to_end(&mut bytes)
+        .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+    let mut read = complete_utf8_len(&bytes[..(want as usize).min(bytes.len())]);
+    if read == 0 && !bytes.is_empty() {
+        read = utf8_width(bytes[0]).min(bytes.len());
+    }
+    let offset = start + read as u64;
+    Ok(LogChunk {
+        data: String::from_utf8_lossy(&bytes[..read]).into_owned(),
+        offset,
+        reset,
+        eof: offset >= len,
+    })
+}
+
+/// Length of `bytes` without a trailing, incomplete UTF-8 character, which
+/// is left for the next read.
+fn complete_utf8_len(bytes: &[u8]) -> usize {
+    let tail = bytes.len().saturating_sub(4);
+    for index in (tail..bytes.len()).rev() {
+        if (0x80..=0xBF).contains(&bytes[index]) {
+            continue;
+        }
+        let width = utf8_width(bytes[index]);
+        return if index + width > bytes.len() {
+            index
+        } else {
+            bytes.len()
+        };
+    }
+    bytes.len()
+}
+
+/// Width of the UTF-8 character starting with `lead`; stray continuation and
+/// invalid bytes count as one.
+fn utf8_width(lead: u8) -> usize {
+    match lead {
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF7 => 4,
+        _ => 1,
+    }
+}
+
+fn open(path: &Path) -> std::io::Result<LogFile> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let size = file.metadata()?.len();
+    Ok(LogFile {
+        path: path.to_path_buf(),
+        file,
+        size,
+    })
+}
+
+/// Shifts `app.log` to `app.log.1`, `app.log.1` to `app.log.2` and so on,
+/// dropping the oldest, then opens a fresh file.
+fn rotate(path: &Path) -> std::io::Result<LogFile> {
+    let rotated = |index: usize| PathBuf::from(format!("{}.{index}", path.display()));
+    let _ = fs::remove_file(rotated(KEEP_ROTATED));
+    for index in (1..KEEP_ROTATED).rev() {
+        let _ = fs::rename(rotated(index), rotated(index + 1));
+    }
+    fs::rename(path, rotated(1))?;
+    open(path)
+}
@@ -1,52 +1,847 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod cli_manager;
+mod config_watcher;
+mod dialogs;
+mod log_file;
+mod tray;
+mod updates;
+mod window_state;
 
-use cli_manager::{CliProcessManager, CliStatus};
+use cli_manager::{
+    ensure_config_file, entry_candidates, resolve_allowed_external_schemes, resolve_allowed_hosts,
+    resolve_config_path, resolve_keep_server_on_close, resolve_listening_host,
+    resolve_listening_mode, resolve_log_file, resolve_nav_telemetry,
+    resolve_quit_on_last_window_close, resolve_restart_on_config_change, set_listening_mode,
+    validate_config, CandidateReport, CliDiagnostics, CliProcessManager, CliState, CliStatus,
+    CommandResult, LogLine, LogsPage, OriginInfo, ResourceUsage, MAIN_INSTANCE,
+};
+use config_watcher::{ConfigChange, ConfigWatcher};
+use log::{debug, info, warn};
+use parking_lot::Mutex;
 use serde_json::json;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use tauri::menu::{MenuBuilder, MenuItem, SubmenuBuilder};
 use tauri::plugin::{Builder as PluginBuilder, TauriPlugin};
-use tauri::webview::Webview;
-use tauri::{AppHandle, Emitter, Manager, Runtime, Wry};
+use tauri::webview::{
+    DownloadEvent, PageLoadEvent, PageLoadPayload, Webview, WebviewWindowBuilder,
+};
+use tauri::{AppHandle, Emitter, Listener, Manager, Runtime, WebviewUrl, Wry};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
 use tauri_plugin_opener::OpenerExt;
 use url::Url;
 
+/// CLI managers keyed by instance id. An instance id doubles as the label of
+/// the window its backend serves; the main window uses `MAIN_INSTANCE`.
 #[derive(Clone)]
 pub struct AppState {
-    pub manager: CliProcessManager,
+    instances: Arc<Mutex<HashMap<String, CliProcessManager>>>,
+    /// Windows opened with `open_new_window`, keyed by label, with the
+    /// instance whose backend they show.
+    windows: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl AppState {
+    fn new() -> Self {
+        let mut instances = HashMap::new();
+        instances.insert(
+            MAIN_INSTANCE.to_string(),
+            CliProcessManager::new(MAIN_INSTANCE),
+        );
+        Self {
+            instances: Arc::new(Mutex::new(instances)),
+            windows: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// The instance a window belongs to: the one it was opened for, or the
+    /// instance named by its label.
+    fn instance_for_window(&self, label: &str) -> String {
+        self.windows
+            .lock()
+            .get(label)
+            .cloned()
+            .unwrap_or_else(|| label.to_string())
+    }
+
+    /// Returns the manager for `instance_id`, creating it on first use.
+    pub fn manager(&self, instance_id: &str) -> CliProcessManager {
+        self.instances
+            .lock()
+            .entry(instance_id.to_string())
+            .or_insert_with(|| CliProcessManager::new(instance_id))
+            .clone()
+    }
+
+    pub fn main_manager(&self) -> CliProcessManager {
+        self.manager(MAIN_INSTANCE)
+    }
+
+    fn existing(&self, instance_id: &str) -> Option<CliProcessManager> {
+        self.instances.lock().get(instance_id).cloned()
+    }
+
+    fn remove(&self, instance_id: &str) -> Option<CliProcessManager> {
+        self.instances.lock().remove(instance_id)
+    }
+
+    fn all(&self) -> Vec<CliProcessManager> {
+        self.instances.lock().values().cloned().collect()
+    }
+}
+
+/// Holds the config file watcher so it can be torn down on exit.
+struct ConfigWatcherState(Mutex<Option<ConfigWatcher>>);
+
+/// Commands default to the instance tied to the calling window.
+fn resolve_instance_id(
+    window: &tauri::Window,
+    instance_id: Option<String>,
+) -> Result<String, String> {
+    let id = instance_id.unwrap_or_else(|| match window.try_state::<AppState>() {
+        Some(state) => state.instance_for_window(window.label()),
+        None => window.label().to_string(),
+    });
+    let valid = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'));
+    if valid {
+        Ok(id)
+    } else {
+        Err(format!("invalid instance id: {id:?}"))
+    }
+}
+
+#[tauri::command]
+fn cli_get_status(
+    window: tauri::Window,
+    state: tauri::State<AppState>,
+    instance_id: Option<String>,
+) -> Result<CliStatus, String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    Ok(state
+        .existing(&id)
+        .map(|manager| manager.status())
+        .unwrap_or_default())
+}
+
+#[tauri::command]
+fn cli_get_logs(
+    window: tauri::Window,
+    state: tauri::State<AppState>,
+    instance_id: Option<String>,
+) -> Result<Vec<LogLine>, String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    Ok(state
+        .existing(&id)
+        .map(|manager| manager.logs())
+        .unwrap_or_default())
+}
+
+#[tauri::command]
+fn cli_get_diagnostics(
+    app: AppHandle,
+    window: tauri::Window,
+    state: tauri::State<AppState>,
+    instance_id: Option<String>,
+) -> Result<CliDiagnostics, String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    // Don't register a manager just to describe it.
+    let manager = state
+        .existing(&id)
+        .unwrap_or_else(|| CliProcessManager::new(&id));
+    manager
+        .diagnostics(&app, is_dev_mode())
+        .map_err(|e| e.to_string())
+}
+
+/// Writes a diagnostics zip to `dest` (a file, or a directory to put one in)
+/// and returns its path.
+#[tauri::command]
+async fn cli_export_diagnostics(
+    app: AppHandle,
+    window: tauri::Window,
+    state: tauri::State<'_, AppState>,
+    dest: String,
+    instance_id: Option<String>,
+) -> Result<String, String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    let manager = state
+        .existing(&id)
+        .unwrap_or_else(|| CliProcessManager::new(&id));
+    let dest = std::path::PathBuf::from(dest);
+    tauri::async_runtime::spawn_blocking(move || {
+        manager
+            .export_diagnostics(&app, is_dev_mode(), &dest)
+            .map(|path| path.to_string_lossy().into_owned())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+#[tauri::command]
+fn cli_copy_url(
+    window: tauri::Window,
+    state: tauri::State<AppState>,
+    instance_id: Option<String>,
+) -> Result<String, String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    let status = state
+        .existing(&id)
+        .map(|manager| manager.status())
+        .unwrap_or_default();
+    server_url(&status)
+}
+
+/// Opens the backend in the system browser, for its devtools and extensions.
+#[tauri::command]
+fn cli_open_in_browser(
+    app: AppHandle,
+    window: tauri::Window,
+    state: tauri::State<AppState>,
+    instance_id: Option<String>,
+) -> Result<(), String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    let status = state
+        .existing(&id)
+        .map(|manager| manager.status())
+        .unwrap_or_default();
+    open_in_browser(&app, &status)
+}
+
+fn open_in_browser(app: &AppHandle, status: &CliStatus) -> Result<(), String> {
+    let url = server_url(status)?;
+    app.opener()
+        .open_url(url.as_str(), None::<&str>)
+        .map_err(|err| format!("failed to open {url} in the browser: {err}"))
+}
+
+fn server_url(status: &CliStatus) -> Result<String, String> {
+    match (&status.state, &status.url) {
+        (CliState::Ready, Some(url)) => Ok(url.clone()),
+        (state, _) => Err(format!(
+            "The CLI server URL is not available yet (state: {})",
+            serde_json::to_value(state)
+                .ok()
+                .and_then(|value| value.as_str().map(str::to_string))
+                .unwrap_or_default()
+        )),
+    }
+}
+
+/// The backend origin next to the calling webview's, so the frontend knows
+/// whether same-origin requests and cookies work.
+#[tauri::command]
+fn cli_origin_info(
+    window: tauri::Window,
+    webview: Webview,
+    state: tauri::State<AppState>,
+    instance_id: Option<String>,
+) -> Result<OriginInfo, String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    let backend_url = state
+        .existing(&id)
+        .and_then(|manager| server_url(&manager.status()).ok());
+    Ok(OriginInfo::new(
+        backend_url.as_deref(),
+        webview.url().ok().as_ref(),
+    ))
+}
+
+/// Tails the log file: up to `max_bytes` from `offset`, plus the offset to
+/// pass next time.
+#[tauri::command]
+fn cli_read_log_file(offset: u64, max_bytes: u64) -> Result<log_file::LogChunk, String> {
+    log_file::read_chunk(offset, max_bytes)
+}
+
+#[tauri::command]
+fn cli_open_config(app: AppHandle) -> Result<(), String> {
+    open_config(&app)
+}
+
+/// Reveals the log file in the file manager, or explains how to enable one.
+fn show_log_file(app: &AppHandle) {
+    let Some(path) = log_file::path() else {
+        dialogs::show(
+            app,
+            "Show Log File",
+            "No log file is configured. Set preferences.logFile in the config file to keep one.",
+            MessageDialogKind::Info,
+        );
+        return;
+    };
+    if let Err(err) = app.opener().reveal_item_in_dir(&path) {
+        warn!("failed to reveal {}: {err}", path.display());
+    }
+}
+
+/// Shows the resolved CLI entry selected in the file manager, falling back to
+/// opening its folder.
+fn reveal_entry(app: &AppHandle, manager: &CliProcessManager) -> Result<(), String> {
+    let path = manager
+        .entry_path(app, is_dev_mode())
+        .map_err(|err| err.to_string())?;
+    if app.opener().reveal_item_in_dir(&path).is_ok() {
+        return Ok(());
+    }
+    let dir = path.parent().unwrap_or(&path);
+    app.opener()
+        .open_path(dir.to_string_lossy(), None::<&str>)
+        .map_err(|err| format!("failed to open {}: {err}", dir.display()))
+}
+
+#[tauri::command]
+fn cli_reveal_entry(
+    app: AppHandle,
+    window: tauri::Window,
+    state: tauri::State<AppState>,
+    instance_id: Option<String>,
+) -> Result<(), String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    let manager = state
+        .existing(&id)
+        .unwrap_or_else(|| CliProcessManager::new(&id));
+    reveal_entry(&app, &manager)
+}
+
+fn open_config(app: &AppHandle) -> Result<(), String> {
+    let path = ensure_config_file()?;
+    app.opener()
+        .open_path(path.to_string_lossy(), None::<&str>)
+        .map_err(|err| format!("failed to open {}: {err}", path.display()))
+}
+
+/// Runs the CLI with a one-shot flag such as `--version` and returns its
+/// output.
+#[tauri::command]
+async fn cli_run_command(
+    app: AppHandle,
+    window: tauri::Window,
+    state: tauri::State<'_, AppState>,
+    args: Vec<String>,
+    instance_id: Option<String>,
+) -> Result<CommandResult, String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    let manager = state
+        .existing(&id)
+        .unwrap_or_else(|| CliProcessManager::new(&id));
+    tauri::async_runtime::spawn_blocking(move || manager.run_command(&app, is_dev_mode(), args))
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+#[tauri::command]
+fn cli_list_entry_candidates() -> Vec<CandidateReport> {
+    entry_candidates()
 }
 
 #[tauri::command]
-fn cli_get_status(state: tauri::State<AppState>) -> CliStatus {
-    state.manager.status()
+fn cli_validate_config() -> Result<(), String> {
+    validate_config()
 }
 
 #[tauri::command]
-fn cli_restart(app: AppHandle, state: tauri::State<AppState>) -> Result<CliStatus, String> {
-    let dev_mode = is_dev_mode();
-    state.manager.stop().map_err(|e| e.to_string())?;
+fn cli_get_logs_since(
+    window: tauri::Window,
+    state: tauri::State<AppState>,
+    cursor: u64,
+    instance_id: Option<String>,
+) -> Result<LogsPage, String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    Ok(state
+        .existing(&id)
+        .map(|manager| manager.logs_since(cursor))
+        .unwrap_or(LogsPage {
+            lines: Vec::new(),
+            cursor,
+        }))
+}
+
+/// Proxies a request to the instance's CLI so the frontend does not depend on
+/// the webview and backend sharing an origin.
+#[tauri::command]
+async fn cli_request(
+    window: tauri::Window,
+    state: tauri::State<'_, AppState>,
+    method: String,
+    path: String,
+    body: Option<serde_json::Value>,
+    instance_id: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    let manager = state.existing(&id).ok_or("CLI is not running")?;
+    tauri::async_runtime::spawn_blocking(move || manager.request(&method, &path, body))
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+#[tauri::command]
+fn cli_get_resource_usage(
+    window: tauri::Window,
+    state: tauri::State<AppState>,
+    instance_id: Option<String>,
+) -> Result<Option<ResourceUsage>, String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    Ok(state
+        .existing(&id)
+        .and_then(|manager| manager.resource_usage()))
+}
+
+#[tauri::command]
+fn cli_set_log_streaming(
+    window: tauri::Window,
+    state: tauri::State<AppState>,
+    enabled: bool,
+    instance_id: Option<String>,
+) -> Result<(), String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    state.manager(&id).set_log_streaming(enabled);
+    Ok(())
+}
+
+#[tauri::command]
+fn cli_get_listening_mode() -> String {
+    resolve_listening_mode()
+}
+
+/// Persists the mode; running CLIs keep their host until restarted.
+#[tauri::command]
+fn cli_set_listening_mode(mode: String) -> Result<(), String> {
+    set_listening_mode(&mode)
+}
+
+/// `workspace` switches the instance to another project folder; an empty
+/// string clears it and omitting it keeps the current one. `extra_args`
+/// likewise replaces the extra CLI arguments, with an empty list clearing
+/// them. `dev` overrides the detected dev mode for this launch only, when
+/// the build enables the `dev-toggle` feature.
+#[tauri::command]
+fn cli_restart(
+    app: AppHandle,
+    window: tauri::Window,
+    state: tauri::State<AppState>,
+    instance_id: Option<String>,
+    workspace: Option<String>,
+    extra_args: Option<Vec<String>>,
+    dev: Option<bool>,
+) -> Result<CliStatus, String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    let dev_mode = dev.unwrap_or_else(is_dev_mode);
+    if dev_mode != is_dev_mode() && !cfg!(feature = "dev-toggle") {
+        return Err("switching dev mode is disabled in this build".to_string());
+    }
+    let manager = state.manager(&id);
+    if let Some(extra_args) = extra_args {
+        manager.set_extra_args(Some(extra_args).filter(|args| !args.is_empty()))?;
+    }
+    if let Some(workspace) = workspace {
+        manager.set_workspace(Some(workspace));
+    }
+    manager.stop().map_err(|e| e.to_string())?;
+    manager.start(app, dev_mode).map_err(|e| e.to_string())?;
+    Ok(manager.status())
+}
+
+/// Restarts the CLI only when it is not `Ready` or fails a health probe, so a
+/// watchdog does not kill a healthy server. A launch in progress is left
+/// alone, and an unhealthy external server is reported rather than replaced.
+/// A restart keeps the mode the CLI last ran in.
+#[tauri::command]
+async fn cli_restart_if_unhealthy(
+    app: AppHandle,
+    window: tauri::Window,
+    state: tauri::State<'_, AppState>,
+    instance_id: Option<String>,
+) -> Result<CliStatus, String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    let (manager, dev_mode) = match state.existing(&id) {
+        Some(manager) => {
+            let dev = manager.status().dev;
+            (manager, dev)
+        }
+        None => (state.manager(&id), is_dev_mode()),
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        if manager.is_healthy() {
+            return Ok(manager.status());
+        }
+        if manager.is_attached_externally() {
+            return Err("attached CodeNomad server is not responding".to_string());
+        }
+        warn!("cli is unhealthy, restarting");
+        manager.stop().map_err(|e| e.to_string())?;
+        manager.start(app, dev_mode).map_err(|e| e.to_string())?;
+        Ok(manager.status())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+/// Resolves once the CLI is ready; fails if it errors or stops first, or after
+/// `timeout_ms` (the startup timeout by default).
+#[tauri::command]
+async fn cli_wait_ready(
+    window: tauri::Window,
+    state: tauri::State<'_, AppState>,
+    timeout_ms: Option<u64>,
+    instance_id: Option<String>,
+) -> Result<CliStatus, String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    let manager = state.existing(&id).ok_or("CLI is not running")?;
+    tauri::async_runtime::spawn_blocking(move || {
+        manager.wait_ready(timeout_ms.map(Duration::from_millis))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+/// Points the instance at a CodeNomad server started elsewhere, after checking
+/// that one answers at `url`.
+#[tauri::command]
+async fn cli_attach(
+    app: AppHandle,
+    window: tauri::Window,
+    state: tauri::State<'_, AppState>,
+    url: String,
+    instance_id: Option<String>,
+) -> Result<CliStatus, String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    let manager = state.manager(&id);
+    tauri::async_runtime::spawn_blocking(move || manager.attach_url(&app, &url))
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+/// Dismisses an `Error` status once the user has seen it.
+#[tauri::command]
+fn cli_clear_error(
+    app: AppHandle,
+    window: tauri::Window,
+    state: tauri::State<AppState>,
+    instance_id: Option<String>,
+) -> Result<CliStatus, String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    match state.existing(&id) {
+        Some(manager) => manager.clear_error(&app),
+        None => Ok(CliStatus::default()),
+    }
+}
+
+/// Leaves an attached server running and returns the window to the app shell.
+#[tauri::command]
+fn cli_detach(
+    app: AppHandle,
+    window: tauri::Window,
+    state: tauri::State<AppState>,
+    instance_id: Option<String>,
+) -> Result<CliStatus, String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    let manager = state.existing(&id).ok_or("CLI is not running")?;
+    let status = manager.detach(&app)?;
+    if let Some(window) = app.get_webview_window(&id) {
+        let _ = window.navigate(app_shell_url());
+    }
+    Ok(status)
+}
+
+/// The bundled loading page, which waits for a backend to become ready.
+fn app_shell_url() -> Url {
+    let origin = if cfg!(windows) {
+        "http://tauri.localhost"
+    } else {
+        "tauri://localhost"
+    };
+    Url::parse(&format!("{origin}/loading.html")).expect("valid app shell URL")
+}
+
+/// Applies config changes without restarting the process, when the CLI
+/// supports it; otherwise the error asks for a restart.
+#[tauri::command]
+async fn cli_reload(
+    app: AppHandle,
+    window: tauri::Window,
+    state: tauri::State<'_, AppState>,
+    instance_id: Option<String>,
+) -> Result<(), String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    let manager = state.existing(&id).ok_or("CLI is not running")?;
+    tauri::async_runtime::spawn_blocking(move || manager.reload(&app))
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+#[tauri::command]
+fn cli_stop(
+    app: AppHandle,
+    window: tauri::Window,
+    state: tauri::State<AppState>,
+    instance_id: Option<String>,
+) -> Result<CliStatus, String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    let Some(manager) = state.existing(&id) else {
+        return Ok(CliStatus::default());
+    };
+    manager.stop().map_err(|e| e.to_string())?;
+    let status = manager.status();
+    manager.emit_status(&app, &status);
+    Ok(status)
+}
+
+fn restart_manager(app: AppHandle, manager: CliProcessManager) {
+    std::thread::spawn(move || {
+        let dev_mode = is_dev_mode();
+        let result = manager
+            .stop()
+            .and_then(|_| manager.start(app.clone(), dev_mode));
+        if let Err(err) = result {
+            let _ = app.emit("cli:error", json!({"message": err.to_string()}));
+        }
+    });
+}
+
+fn stop_manager(app: AppHandle, manager: CliProcessManager) {
+    std::thread::spawn(move || {
+        if let Err(err) = manager.stop() {
+            let _ = app.emit("cli:error", json!({"message": err.to_string()}));
+        }
+        manager.emit_status(&app, &manager.status());
+    });
+}
+
+/// Opens another window on the calling window's backend. It shows the backend
+/// directly when it is ready and the loading page otherwise, which follows
+/// the backend once it is.
+#[tauri::command]
+async fn open_new_window(
+    app: AppHandle,
+    window: tauri::Window,
+    instance_id: Option<String>,
+) -> Result<(), String> {
+    let id = resolve_instance_id(&window, instance_id)?;
+    open_window(&app, &id)
+}
+
+fn open_window(app: &AppHandle, instance_id: &str) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let url = state
+        .existing(instance_id)
+        .and_then(|manager| server_url(&manager.status()).ok())
+        .and_then(|url| Url::parse(&url).ok())
+        .map(WebviewUrl::External)
+        .unwrap_or_else(|| WebviewUrl::App("loading.html".into()));
+    let label = (2..)
+        .map(|n| format!("window-{n}"))
+        .find(|label| app.get_webview_window(label).is_none() && state.existing(label).is_none())
+        .unwrap_or_default();
+
     state
-        .manager
-        .start(app, dev_mode)
-        .map_err(|e| e.to_string())?;
-    Ok(state.manager.status())
+        .windows
+        .lock()
+        .insert(label.clone(), instance_id.to_string());
+    let built = WebviewWindowBuilder::new(app, &label, url)
+        .title("CodeNomad")
+        .inner_size(1400.0, 900.0)
+        .min_inner_size(800.0, 600.0)
+        .theme(Some(tauri::Theme::Dark))
+        .on_download(handle_download)
+        .build();
+    if let Err(err) = built {
+        state.windows.lock().remove(&label);
+        return Err(format!("failed to open window: {err}"));
+    }
+    Ok(())
+}
+
+pub(crate) fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn handle_config_change(app: &AppHandle, change: ConfigChange) {
+    let _ = app.emit("cli:configChanged", change.clone());
+    if !change
+        .changed
+        .iter()
+        .any(|key| key == "listeningMode" || key == "listeningHost")
+    {
+        return;
+    }
+
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let host = resolve_listening_host();
+    let stale: Vec<CliProcessManager> = state
+        .all()
+        .into_iter()
+        .filter(|manager| {
+            let status = manager.status();
+            status.state != CliState::Stopped && status.host.as_deref() != Some(host.as_str())
+        })
+        .collect();
+    if stale.is_empty() {
+        return;
+    }
+
+    if resolve_restart_on_config_change() {
+        for manager in stale {
+            restart_manager(app.clone(), manager);
+        }
+        return;
+    }
+
+    let app_handle = app.clone();
+    app.dialog()
+        .message(format!(
+            "The listening address changed to {host}. Restart the backend to apply it?"
+        ))
+        .title("Restart Required")
+        .kind(MessageDialogKind::Info)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "Restart".to_string(),
+            "Later".to_string(),
+        ))
+        .show(move |confirmed| {
+            if confirmed {
+                for manager in stale {
+                    restart_manager(app_handle.clone(), manager);
+                }
+            }
+        });
 }
 
 fn is_dev_mode() -> bool {
     cfg!(debug_assertions) || std::env::var("TAURI_DEV").is_ok()
 }
 
-fn should_allow_internal(url: &Url) -> bool {
+/// Started with `--safe-mode` or `CODENOMAD_SAFE_MODE=1`: the CLI is not
+/// started automatically, so a config that crashes it can still be fixed.
+fn is_safe_mode() -> bool {
+    std::env::args().any(|arg| arg == "--safe-mode")
+        || std::env::var("CODENOMAD_SAFE_MODE")
+            .is_ok_and(|value| matches!(value.trim(), "1" | "true"))
+}
+
+/// Started with `--multi-instance` or `CODENOMAD_MULTI_INSTANCE=1`: every
+/// launch gets its own process and backend instead of handing off to the
+/// running app.
+fn allows_multiple_processes() -> bool {
+    std::env::args().any(|arg| arg == "--multi-instance")
+        || std::env::var("CODENOMAD_MULTI_INSTANCE")
+            .is_ok_and(|value| matches!(value.trim(), "1" | "true"))
+}
+
+/// A second launch hands over its arguments and exits; the running app comes
+/// to the front and the frontend decides what to open.
+fn handle_second_instance(app: &AppHandle, argv: Vec<String>, cwd: String) {
+    show_main_window(app);
+    let _ = app.emit("app:secondInstance", json!({"argv": argv, "cwd": cwd}));
+}
+
+fn should_allow_internal(url: &Url, allowed_hosts: &[String], backend_url: Option<&Url>) -> bool {
     match url.scheme() {
         "tauri" | "asset" | "file" => true,
-        "http" | "https" => matches!(url.host_str(), Some("127.0.0.1" | "localhost")),
+        "http" | "https" => {
+            let Some(host) = url.host_str() else {
+                return false;
+            };
+            if matches!(host, "127.0.0.1" | "localhost") {
+                return true;
+            }
+            if allowed_hosts
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(host))
+            {
+                return true;
+            }
+            backend_url.is_some_and(|backend| {
+                backend.host_str() == Some(host)
+                    && backend.port_or_known_default() == url.port_or_known_default()
+            })
+        }
         _ => false,
     }
 }
 
+/// Whether `url` belongs to the app or one of its backends rather than an
+/// external site.
+fn is_internal_url<R: Runtime>(app: &AppHandle<R>, url: &Url) -> bool {
+    let backend_urls: Vec<Url> = app
+        .try_state::<AppState>()
+        .map(|state| state.all())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|manager| manager.status().url)
+        .filter_map(|raw| Url::parse(&raw).ok())
+        .collect();
+    let allowed_hosts = resolve_allowed_hosts();
+    if backend_urls.is_empty() {
+        should_allow_internal(url, &allowed_hosts, None)
+    } else {
+        backend_urls
+            .iter()
+            .any(|backend| should_allow_internal(url, &allowed_hosts, Some(backend)))
+    }
+}
+
+/// Whether the navigation guard would keep `url` in the app (`true`) rather
+/// than hand it to the system, under the current allowlist and backends.
+/// Nothing is opened or emitted.
+#[tauri::command]
+fn nav_would_allow(app: AppHandle, url: String) -> Result<bool, String> {
+    let url = Url::parse(&url).map_err(|err| format!("invalid URL {url:?}: {err}"))?;
+    Ok(is_internal_url(&app, &url))
+}
+
+/// Lets the instance's manager confirm that a backend navigation loaded.
+fn handle_page_load(webview: &Webview<Wry>, payload: &PageLoadPayload<'_>) {
+    if payload.event() != PageLoadEvent::Finished {
+        return;
+    }
+    let app = webview.app_handle();
+    if let Some(manager) = app
+        .try_state::<AppState>()
+        .and_then(|state| state.existing(webview.label()))
+    {
+        manager.page_loaded(app, payload.url());
+    }
+}
+
 fn intercept_navigation<R: Runtime>(webview: &Webview<R>, url: &Url) -> bool {
-    if should_allow_internal(url) {
-        return true;
+    let internal = is_internal_url(webview.app_handle(), url);
+    let opened_externally = !internal && open_external_link(webview, url);
+    if resolve_nav_telemetry() {
+        let _ = webview.emit(
+            "nav:navigated",
+            json!({"url": url.as_str(), "internal": internal, "openedExternally": opened_externally}),
+        );
+    }
+    internal
+}
+
+/// Hands a link the app does not serve to the system, returning whether it
+/// was opened.
+fn open_external_link<R: Runtime>(webview: &Webview<R>, url: &Url) -> bool {
+    // Unknown schemes can make the OS pop up "no app to open this" dialogs.
+    if !resolve_allowed_external_schemes()
+        .iter()
+        .any(|scheme| scheme == url.scheme())
+    {
+        warn!("blocked navigation to {url}: scheme not allowed");
+        let _ = webview.emit(
+            "nav:blocked",
+            json!({"url": url.as_str(), "scheme": url.scheme(), "reason": "schemeNotAllowed"}),
+        );
+        return false;
     }
 
     if let Err(err) = webview
@@ -54,36 +849,193 @@ fn intercept_navigation<R: Runtime>(webview: &Webview<R>, url: &Url) -> bool {
         .opener()
         .open_url(url.as_str(), None::<&str>)
     {
-        eprintln!("[tauri] failed to open external link {}: {}", url, err);
+        warn!("failed to open external link {}: {}", url, err);
+        return false;
+    }
+    true
+}
+
+/// Lets internal downloads save normally and hands external ones to the
+/// system browser, which knows where the user keeps downloads.
+fn handle_download<R: Runtime>(webview: Webview<R>, event: DownloadEvent<'_>) -> bool {
+    match event {
+        DownloadEvent::Requested { url, destination } => {
+            if is_internal_url(webview.app_handle(), &url) {
+                let _ = webview.emit(
+                    "nav:download",
+                    json!({"url": url.as_str(), "status": "started", "destination": destination.to_string_lossy()}),
+                );
+                return true;
+            }
+            let opened = webview
+                .app_handle()
+                .opener()
+                .open_url(url.as_str(), None::<&str>);
+            if let Err(err) = &opened {
+                warn!("failed to open external download {}: {}", url, err);
+            }
+            let _ = webview.emit(
+                "nav:download",
+                json!({"url": url.as_str(), "status": "external", "success": opened.is_ok()}),
+            );
+            false
+        }
+        DownloadEvent::Finished { url, path, success } => {
+            let _ = webview.emit(
+                "nav:download",
+                json!({
+                    "url": url.as_str(),
+                    "status": "finished",
+                    "path": path.map(|p| p.to_string_lossy().to_string()),
+                    "success": success,
+                }),
+            );
+            true
+        }
+        _ => true,
     }
-    false
 }
 
 fn main() {
+    log_file::init();
     let navigation_guard: TauriPlugin<Wry, ()> = PluginBuilder::new("external-link-guard")
         .on_navigation(|webview, url| intercept_navigation(webview, url))
+        .on_page_load(handle_page_load)
         .build();
 
-    tauri::Builder::default()
+    let mut builder = tauri::Builder::default();
+    // Must be registered first so a second launch exits before doing any work.
+    if !allows_multiple_processes() {
+        builder = builder.plugin(tauri_plugin_single_instance::init(handle_second_instance));
+    }
+    #[cfg(feature = "updater")]
+    {
+        builder = builder.plugin(tauri_plugin_updater::Builder::new().build());
+    }
+    builder
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(navigation_guard)
-        .manage(AppState {
-            manager: CliProcessManager::new(),
-        })
+        .manage(AppState::new())
         .setup(|app| {
+            log_file::configure(resolve_log_file());
+            let safe_mode = is_safe_mode();
+            // The main window is created here rather than from the config so
+            // it can get a download handler.
+            if let Some(config) = app
+                .config()
+                .app
+                .windows
+                .iter()
+                .find(|config| config.label == "main")
+                .cloned()
+            {
+                WebviewWindowBuilder::from_config(app.handle(), &config)?
+                    .on_download(handle_download)
+                    .on_page_load(move |window, payload| {
+                        if safe_mode && payload.event() == PageLoadEvent::Finished {
+                            let config_path = resolve_config_path();
+                            let _ = window.emit(
+                                "app:safeMode",
+                                json!({"configPath": config_path.to_string_lossy()}),
+                            );
+                        }
+                    })
+                    .build()?;
+            }
             build_menu(&app.handle())?;
+            if let Some(window) = app.get_webview_window("main") {
+                window_state::restore(&window);
+                let _ = window.show();
+            }
+            if let Err(err) = tray::build(app.handle()) {
+                warn!("failed to create tray icon: {err}");
+            }
+            let watcher_handle = app.handle().clone();
+            let watcher = config_watcher::watch(move |change| {
+                handle_config_change(&watcher_handle, change);
+            });
+            app.manage(ConfigWatcherState(Mutex::new(watcher)));
+            handle_termination_signals(app.handle());
+            if safe_mode {
+                info!("safe mode: not starting the CLI");
+                return Ok(());
+            }
             let dev_mode = is_dev_mode();
             let app_handle = app.handle().clone();
-            let manager = app.state::<AppState>().manager.clone();
+            let manager = app.state::<AppState>().main_manager();
             std::thread::spawn(move || {
                 if let Err(err) = manager.start(app_handle.clone(), dev_mode) {
                     let _ = app_handle.emit("cli:error", json!({"message": err.to_string()}));
+                    dialogs::show(
+                        &app_handle,
+                        "Backend Failed to Start",
+                        format!("CodeNomad could not start its backend:\n{err}"),
+                        MessageDialogKind::Error,
+                    );
                 }
             });
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![cli_get_status, cli_restart])
+        .on_window_event(|window, event| {
+            window_state::track(window, event);
+            // With `keepServerOnClose` and a tray to come back through,
+            // closing the main window only hides it; Quit still stops the CLI.
+            // The same goes when it is the last visible window and the app
+            // outlives its windows (`quitOnLastWindowClose`, off on macOS).
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                let app = window.app_handle();
+                if window.label() != "main" {
+                    return;
+                }
+                let keep_for_tray =
+                    app.tray_by_id(tray::TRAY_ID).is_some() && resolve_keep_server_on_close();
+                let last_visible = app
+                    .webview_windows()
+                    .iter()
+                    .all(|(label, other)| label == "main" || !other.is_visible().unwrap_or(false));
+                if keep_for_tray || (last_visible && !resolve_quit_on_last_window_close()) {
+                    api.prevent_close();
+                    let _ = window.hide();
+                    #[cfg(target_os = "macos")]
+                    if !keep_for_tray {
+                        let _ = app.hide();
+                    }
+                }
+            }
+        })
+        .invoke_handler(tauri::generate_handler![
+            cli_get_status,
+            cli_get_logs,
+            cli_restart,
+            cli_stop,
+            cli_validate_config,
+            cli_get_diagnostics,
+            cli_copy_url,
+            cli_set_log_streaming,
+            cli_get_listening_mode,
+            cli_set_listening_mode,
+            cli_get_logs_since,
+            cli_open_config,
+            cli_get_resource_usage,
+            cli_request,
+            cli_reload,
+            cli_list_entry_candidates,
+            cli_reveal_entry,
+            cli_run_command,
+            cli_export_diagnostics,
+            cli_origin_info,
+            open_new_window,
+            cli_wait_ready,
+            cli_attach,
+            cli_detach,
+            cli_read_log_file,
+            cli_clear_error,
+            cli_restart_if_unhealthy,
+            nav_would_allow,
+            cli_open_in_browser
+        ])
         .on_menu_event(|app_handle, event| {
             match event.id().0.as_str() {
                 // File menu
@@ -92,6 +1044,34 @@ fn main() {
                         let _ = window.emit("menu:newInstance", ());
                     }
                 }
+                "new_window" => {
+                    if let Err(err) = open_window(app_handle, MAIN_INSTANCE) {
+                        warn!("{err}");
+                    }
+                }
+                "open_config" => {
+                    if let Err(err) = open_config(app_handle) {
+                        warn!("{err}");
+                    }
+                }
+                "show_log_file" => show_log_file(app_handle),
+                "reveal_entry" => {
+                    let manager = app_handle.state::<AppState>().main_manager();
+                    if let Err(err) = reveal_entry(app_handle, &manager) {
+                        warn!("{err}");
+                        dialogs::show(
+                            app_handle,
+                            "Reveal CLI Entry",
+                            err,
+                            MessageDialogKind::Error,
+                        );
+                    }
+                }
+                "preferences" => {
+                    if let Some(window) = app_handle.get_webview_window("main") {
+                        let _ = window.emit("menu:openPreferences", ());
+                    }
+                }
                 "close" => {
                     if let Some(window) = app_handle.get_webview_window("main") {
                         let _ = window.close();
@@ -101,6 +1081,21 @@ fn main() {
                     app_handle.exit(0);
                 }
 
+                // Backend controls (tray and View menu)
+                "open_main" => {
+                    show_main_window(app_handle);
+                }
+                "restart_backend" => {
+                    if let Some(state) = app_handle.try_state::<AppState>() {
+                        restart_manager(app_handle.clone(), state.main_manager());
+                    }
+                }
+                "stop_backend" => {
+                    if let Some(state) = app_handle.try_state::<AppState>() {
+                        stop_manager(app_handle.clone(), state.main_manager());
+                    }
+                }
+
                 // View menu
                 "reload" => {
                     if let Some(window) = app_handle.get_webview_window("main") {
@@ -118,11 +1113,49 @@ fn main() {
                     }
                 }
 
+                "copy_server_url" => {
+                    if let Some(state) = app_handle.try_state::<AppState>() {
+                        match server_url(&state.main_manager().status()) {
+                            Ok(url) => {
+                                if let Err(err) = app_handle.clipboard().write_text(url) {
+                                    warn!("failed to copy server URL: {err}");
+                                }
+                            }
+                            Err(message) => warn!("{message}"),
+                        }
+                    }
+                }
+
+                "open_in_browser" => {
+                    if let Some(state) = app_handle.try_state::<AppState>() {
+                        if let Err(message) =
+                            open_in_browser(app_handle, &state.main_manager().status())
+                        {
+                            warn!("{message}");
+                        }
+                    }
+                }
+
                 "toggle_fullscreen" => {
                     if let Some(window) = app_handle.get_webview_window("main") {
                         let _ = window.set_fullscreen(!window.is_fullscreen().unwrap_or(false));
                     }
                 }
+                "zoom_in" => {
+                    if let Some(window) = app_handle.get_webview_window("main") {
+                        window_state::adjust_zoom(&window, window_state::ZOOM_STEP);
+                    }
+                }
+                "zoom_out" => {
+                    if let Some(window) = app_handle.get_webview_window("main") {
+                        window_state::adjust_zoom(&window, -window_state::ZOOM_STEP);
+                    }
+                }
+                "zoom_reset" => {
+                    if let Some(window) = app_handle.get_webview_window("main") {
+                        window_state::reset_zoom(&window);
+                    }
+                }
 
                 // Window menu
                 "minimize" => {
@@ -137,9 +1170,9 @@ fn main() {
                 }
 
                 // App menu (macOS)
+                updates::MENU_ID => updates::check(app_handle),
                 "about" => {
-                    // TODO: Implement about dialog
-                    println!("About menu item clicked");
+                    show_about_dialog(app_handle);
                 }
                 "hide" => {
                     if let Some(window) = app_handle.get_webview_window("main") {
@@ -148,58 +1181,156 @@ fn main() {
                 }
                 "hide_others" => {
                     // TODO: Hide other app windows
-                    println!("Hide Others menu item clicked");
+                    debug!("Hide Others menu item clicked");
                 }
                 "show_all" => {
                     // TODO: Show all app windows
-                    println!("Show All menu item clicked");
+                    debug!("Show All menu item clicked");
                 }
 
                 _ => {
-                    println!("Unhandled menu event: {}", event.id().0);
+                    debug!("unhandled menu event: {}", event.id().0);
                 }
             }
         })
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
         .run(|app_handle, event| match event {
-            tauri::RunEvent::ExitRequested { .. } => {
+            tauri::RunEvent::ExitRequested { code, api, .. } => {
+                // No code means the last window went away rather than Quit.
+                if code.is_none() && !resolve_quit_on_last_window_close() {
+                    api.prevent_exit();
+                    return;
+                }
                 let app = app_handle.clone();
                 std::thread::spawn(move || {
-                    if let Some(state) = app.try_state::<AppState>() {
-                        let _ = state.manager.stop();
-                    }
+                    shut_down(&app);
                     app.exit(0);
                 });
             }
             tauri::RunEvent::WindowEvent {
+                label,
                 event: tauri::WindowEvent::Destroyed,
                 ..
             } => {
-                if app_handle.webview_windows().len() <= 1 {
-                    let app = app_handle.clone();
-                    std::thread::spawn(move || {
-                        if let Some(state) = app.try_state::<AppState>() {
-                            let _ = state.manager.stop();
+                // The destroyed window may or may not still be listed here.
+                let last_window = app_handle
+                    .webview_windows()
+                    .keys()
+                    .all(|other| *other == label)
+                    && resolve_quit_on_last_window_close();
+                let app = app_handle.clone();
+                std::thread::spawn(move || {
+                    if let Some(state) = app.try_state::<AppState>() {
+                        // Extra windows never share a label with an instance,
+                        // so closing one leaves the backend it showed running.
+                        state.windows.lock().remove(&label);
+                        if let Some(manager) = state.remove(&label) {
+                            let _ = manager.stop_for_exit();
+                        }
+                        if last_window {
+                            for manager in state.all() {
+                                let _ = manager.stop_for_exit();
+                            }
                         }
+                    }
+                    if last_window {
                         app.exit(0);
-                    });
+                    }
+                });
+            }
+            #[cfg(target_os = "macos")]
+            tauri::RunEvent::Reopen { .. } => {
+                if app_handle.get_webview_window("main").is_some() {
+                    show_main_window(app_handle);
+                } else if app_handle.webview_windows().is_empty() {
+                    // Every window was closed while the app stayed up.
+                    if let Err(err) = open_window(app_handle, MAIN_INSTANCE) {
+                        warn!("{err}");
+                    }
                 }
             }
             _ => {}
         });
 }
 
+/// Cleanup before the app exits: stops watching the config, saves the window
+/// state and stops every CLI (a detached one is left running).
+fn shut_down(app: &AppHandle) {
+    if let Some(watcher) = app.try_state::<ConfigWatcherState>() {
+        watcher.0.lock().take();
+    }
+    window_state::flush();
+    if let Some(state) = app.try_state::<AppState>() {
+        for manager in state.all() {
+            let _ = manager.stop_for_exit();
+        }
+    }
+}
+
+/// SIGINT, SIGTERM and SIGHUP (console control events on Windows) would end
+/// the process without `ExitRequested`, orphaning the CLI, so they go through
+/// the same cleanup. A second signal exits at once.
+fn handle_termination_signals(app: &AppHandle) {
+    let app = app.clone();
+    let received = AtomicBool::new(false);
+    let result = ctrlc::set_handler(move || {
+        if received.swap(true, Ordering::SeqCst) {
+            warn!("second termination signal, exiting without cleanup");
+            std::process::exit(130);
+        }
+        info!("termination signal received, stopping the CLI");
+        // Cleanup runs off the handler thread, which must stay free to take
+        // the second signal.
+        let app = app.clone();
+        std::thread::spawn(move || {
+            shut_down(&app);
+            app.exit(0);
+        });
+    });
+    if let Err(err) = result {
+        warn!("failed to install the termination signal handler: {err}");
+    }
+}
+
+fn show_about_dialog(app: &AppHandle) {
+    let version = app.package_info().version.to_string();
+    let mut message = format!("CodeNomad\nVersion {version}");
+    if let Some(cli_version) = app
+        .try_state::<AppState>()
+        .and_then(|state| state.main_manager().status().cli_version)
+    {
+        message.push_str(&format!("\nCLI {cli_version}"));
+    }
+    dialogs::show(app, "About CodeNomad", message, MessageDialogKind::Info);
+}
+
 fn build_menu(app: &AppHandle) -> tauri::Result<()> {
     let is_mac = cfg!(target_os = "macos");
 
     // Create submenus
     let mut submenus = Vec::new();
 
+    // Preferences lives in the app menu on macOS and the File menu elsewhere
+    let preferences_item = MenuItem::with_id(
+        app,
+        "preferences",
+        "Preferences…",
+        true,
+        Some("CmdOrCtrl+,"),
+    )?;
+    
+    // Check for Updates sits under About: in the app menu on macOS and the
+    // Help menu elsewhere
+    let check_updates_item = updates::menu_item(app)?;
+
     // App menu (macOS only)
     if is_mac {
         let app_menu = SubmenuBuilder::new(app, "CodeNomad")
             .text("about", "About CodeNomad")
+            .item(&check_updates_item)
+            .separator()
+            .item(&preferences_item)
             .separator()
             .text("hide", "Hide CodeNomad")
             .text("hide_others", "Hide Others")
@@ -218,10 +1349,23 @@ fn build_menu(app: &AppHandle) -> tauri::Result<()> {
         true,
         Some("CmdOrCtrl+N")
     )?;
-    
-    let file_menu = SubmenuBuilder::new(app, "File")
+
+    let new_window_item = MenuItem::with_id(
+        app,
+        "new_window",
+        "New Window",
+        true,
+        Some("CmdOrCtrl+Shift+N"),
+    )?;
+
+    let mut file_menu = SubmenuBuilder::new(app, "File")
         .item(&new_instance_item)
-        .separator()
+        .item(&new_window_item)
+        .separator();
+    if !is_mac {
+        file_menu = file_menu.item(&preferences_item).separator();
+    }
+    let file_menu = file_menu
         .text(if is_mac { "close" } else { "quit" }, if is_mac { "Close" } else { "Quit" })
         .build()?;
     submenus.push(file_menu);
@@ -239,18 +1383,65 @@ fn build_menu(app: &AppHandle) -> tauri::Result<()> {
         .build()?;
     submenus.push(edit_menu);
 
-    // View menu
+    // View menu. The backend items follow the main instance: "Copy Server
+    // URL" and "Open in Browser" need a ready CLI and "Restart Backend" is off
+    // while one is starting.
+    let copy_url_item = MenuItem::with_id(
+        app,
+        "copy_server_url",
+        "Copy Server URL",
+        false,
+        None::<&str>,
+    )?;
+    let open_in_browser_item = MenuItem::with_id(
+        app,
+        "open_in_browser",
+        "Open in Browser",
+        false,
+        None::<&str>,
+    )?;
+    let restart_item = MenuItem::with_id(
+        app,
+        "restart_backend",
+        "Restart Backend",
+        true,
+        Some("CmdOrCtrl+Alt+R"),
+    )?;
     let view_menu = SubmenuBuilder::new(app, "View")
         .text("reload", "Reload")
         .text("force_reload", "Force Reload")
         .text("toggle_devtools", "Toggle Developer Tools")
         .separator()
-
+        .item(&copy_url_item)
+        .item(&open_in_browser_item)
+        .item(&restart_item)
+        .separator()
+        .item(&MenuItem::with_id(
+            app,
+            "zoom_reset",
+            "Actual Size",
+            true,
+            Some("CmdOrCtrl+0"),
+        )?)
+        .item(&MenuItem::with_id(
+            app,
+            "zoom_in",
+            "Zoom In",
+            true,
+            Some("CmdOrCtrl+="),
+        )?)
+        .item(&MenuItem::with_id(
+            app,
+            "zoom_out",
+            "Zoom Out",
+            true,
+            Some("CmdOrCtrl+-"),
+        )?)
         .separator()
         .text("toggle_fullscreen", "Toggle Full Screen")
         .build()?;
     submenus.push(view_menu);
-
+    
     // Window menu
     let window_menu = SubmenuBuilder::new(app, "Window")
         .text("minimize", "Minimize")
@@ -258,10 +1449,46 @@ fn build_menu(app: &AppHandle) -> tauri::Result<()> {
         .build()?;
     submenus.push(window_menu);
 
+    // Help menu (About lives in the app menu on macOS)
+    let mut help_menu = SubmenuBuilder::new(app, "Help")
+        .text("open_config", "Open Config File")
+        .text("show_log_file", "Show Log File")
+        .text("reveal_entry", "Reveal CLI Entry");
+    if !is_mac {
+        help_menu = help_menu
+            .separator()
+            .item(&check_updates_item)
+            .text("about", "About CodeNomad");
+    }
+    submenus.push(help_menu.build()?);
+
     // Build the main menu with all submenus
     let submenu_refs: Vec<&dyn tauri::menu::IsMenuItem<_>> = submenus.iter().map(|s| s as &dyn tauri::menu::IsMenuItem<_>).collect();
     let menu = MenuBuilder::new(app).items(&submenu_refs).build()?;
-    
+
     app.set_menu(menu)?;
+
+    // `set_enabled` blocks on the main thread and this listener runs on the
+    // emitting thread, so the update is queued rather than applied here.
+    let handle = app.clone();
+    app.listen_any("cli:status", move |event| {
+        let payload = serde_json::from_str::<serde_json::Value>(event.payload()).ok();
+        let state = payload
+            .as_ref()
+            .and_then(|status| status.get("state")?.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let copy_url_item = copy_url_item.clone();
+        let open_in_browser_item = open_in_browser_item.clone();
+        let restart_item = restart_item.clone();
+        let _ = handle.run_on_main_thread(move || {
+            let _ = copy_url_item.set_enabled(state == "ready");
+            let _ = open_in_browser_item.set_enabled(state == "ready");
+            let _ = restart_item.set_enabled(!matches!(
+                state.as_str(),
+                "starting" | "spawning" | "booting"
+            ));
+        });
+    });
     Ok(())
 }
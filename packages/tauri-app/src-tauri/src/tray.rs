@@ -0,0 +1,76 @@
+use serde_json::Value;
+use tauri::menu::{MenuBuilder, MenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Listener};
+
+pub const TRAY_ID: &str = "main-tray";
+
+fn state_label(state: &str) -> &'static str {
+    match state {
+        "starting" | "spawning" | "booting" => "Starting…",
+        "ready" => "Running",
+        "error" => "Error",
+        _ => "Stopped",
+    }
+}
+
+/// Builds the tray icon. Its menu items share ids with the app menu so they
+/// are handled by the same `on_menu_event` arms.
+pub fn build(app: &AppHandle) -> tauri::Result<()> {
+    let status_item = MenuItem::with_id(
+        app,
+        "tray_status",
+        format!("Backend: {}", state_label("stopped")),
+        false,
+        None::<&str>,
+    )?;
+    let menu = MenuBuilder::new(app)
+        .item(&status_item)
+        .separator()
+        .text("open_main", "Open CodeNomad")
+        .text("restart_backend", "Restart CLI")
+        .text("stop_backend", "Stop CLI")
+        .separator()
+        .text("quit", "Quit")
+        .build()?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .menu(&menu)
+        .tooltip("CodeNomad — Stopped")
+        .show_menu_on_left_click(false)
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                crate::show_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+
+    // Only the main instance emits the plain `cli:status` event. Listeners
+    // run inside `emit` on the emitting thread, and the tray setters block on
+    // the main thread, so the update is queued there instead.
+    let app_handle = app.clone();
+    app.listen_any("cli:status", move |event| {
+        let state = serde_json::from_str::<Value>(event.payload())
+            .ok()
+            .and_then(|status| status.get("state")?.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let label = state_label(&state);
+        let status_item = status_item.clone();
+        let handle = app_handle.clone();
+        let _ = app_handle.run_on_main_thread(move || {
+            let _ = status_item.set_text(format!("Backend: {label}"));
+            if let Some(tray) = handle.tray_by_id(TRAY_ID) {
+                let _ = tray.set_tooltip(Some(format!("CodeNomad — {label}")));
+            }
+        });
+    });
+    Ok(())
+}
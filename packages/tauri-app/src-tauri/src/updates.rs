@@ -0,0 +1,126 @@
+use log::info;
+#[cfg(not(feature = "updater"))]
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tauri::menu::MenuItem;
+use tauri::{AppHandle, Emitter, Listener, Manager, Wry};
+
+pub const MENU_ID: &str = "check_updates";
+
+/// Without the updater plugin the frontend runs the check, and is expected to
+/// emit `update:checkFinished` when done. The item is re-enabled after this
+/// long in case it never does.
+#[cfg(not(feature = "updater"))]
+const FRONTEND_CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// The "Check for Updates…" menu item, disabled while a check is in flight.
+struct UpdateCheck {
+    item: MenuItem<Wry>,
+    in_flight: Arc<AtomicBool>,
+    /// Counts checks, so a timeout only ends the check that started it.
+    #[cfg(not(feature = "updater"))]
+    started: Arc<AtomicU64>,
+}
+
+/// Creates the menu item and registers it so `check` can toggle it.
+pub fn menu_item(app: &AppHandle) -> tauri::Result<MenuItem<Wry>> {
+    let item = MenuItem::with_id(app, MENU_ID, "Check for Updates…", true, None::<&str>)?;
+    app.manage(UpdateCheck {
+        item: item.clone(),
+        in_flight: Arc::new(AtomicBool::new(false)),
+        #[cfg(not(feature = "updater"))]
+        started: Arc::new(AtomicU64::new(0)),
+    });
+    let handle = app.clone();
+    app.listen_any("update:checkFinished", move |_| finish(&handle));
+    Ok(item)
+}
+
+/// Emits `menu:checkUpdates` to the main window so the frontend can react,
+/// and with the `updater` feature also checks through `tauri-plugin-updater`,
+/// reporting `update:checking`, then `update:available`,
+/// `update:notAvailable` or `update:error`. Ignored while a check is running.
+pub fn check(app: &AppHandle) {
+    let Some(state) = app.try_state::<UpdateCheck>() else {
+        return;
+    };
+    if state.in_flight.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let _ = state.item.set_enabled(false);
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit("menu:checkUpdates", ());
+    }
+
+    #[cfg(feature = "updater")]
+    {
+        let handle = app.clone();
+        tauri::async_runtime::spawn(async move {
+            check_with_plugin(&handle).await;
+            finish(&handle);
+        });
+    }
+
+    #[cfg(not(feature = "updater"))]
+    {
+        let handle = app.clone();
+        let in_flight = state.in_flight.clone();
+        let started = state.started.clone();
+        let check = started.fetch_add(1, Ordering::SeqCst) + 1;
+        std::thread::spawn(move || {
+            std::thread::sleep(FRONTEND_CHECK_TIMEOUT);
+            if in_flight.load(Ordering::SeqCst) && started.load(Ordering::SeqCst) == check {
+                info!("no update:checkFinished from the frontend, re-enabling the menu item");
+                finish(&handle);
+            }
+        });
+    }
+}
+
+#[cfg(feature = "updater")]
+async fn check_with_plugin(app: &AppHandle) {
+    use log::warn;
+    use serde_json::json;
+    use tauri_plugin_updater::UpdaterExt;
+
+    let _ = app.emit("update:checking", ());
+    let result = match app.updater() {
+        Ok(updater) => updater.check().await,
+        Err(err) => Err(err),
+    };
+    match result {
+        Ok(Some(update)) => {
+            info!("update available: {}", update.version);
+            let _ = app.emit(
+                "update:available",
+                json!({
+                    "version": update.version,
+                    "currentVersion": update.current_version,
+                    "notes": update.body,
+                    "date": update.date.map(|date| date.to_string()),
+                }),
+            );
+        }
+        Ok(None) => {
+            let current = app.package_info().version.to_string();
+            let _ = app.emit("update:notAvailable", json!({"currentVersion": current}));
+        }
+        Err(err) => {
+            warn!("update check failed: {err}");
+            let _ = app.emit("update:error", json!({"message": err.to_string()}));
+        }
+    }
+}
+
+/// Called from the async runtime or a timer thread, so the menu item is
+/// re-enabled on the main thread.
+fn finish(app: &AppHandle) {
+    if let Some(state) = app.try_state::<UpdateCheck>() {
+        state.in_flight.store(false, Ordering::SeqCst);
+        let item = state.item.clone();
+        let _ = app.run_on_main_thread(move || {
+            let _ = item.set_enabled(true);
+        });
+    }
+}
@@ -0,0 +1,215 @@
+use crate::cli_manager::resolve_config_path;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tauri::{PhysicalPosition, PhysicalSize, Runtime, WebviewWindow, Window, WindowEvent};
+
+const STATE_FILE_NAME: &str = "window-state.json";
+const SAVE_THROTTLE: Duration = Duration::from_millis(500);
+const MIN_ZOOM: f64 = 0.5;
+const MAX_ZOOM: f64 = 3.0;
+pub const ZOOM_STEP: f64 = 0.1;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+    /// Webview zoom factor; absent means 1.0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zoom: Option<f64>,
+}
+
+#[derive(Default)]
+struct Tracker {
+    geometry: Option<WindowGeometry>,
+    last_saved: Option<Instant>,
+}
+
+static TRACKER: Lazy<Mutex<Tracker>> = Lazy::new(|| Mutex::new(Tracker::default()));
+
+/// Stored next to the config file, e.g. `~/.config/codenomad/window-state.json`.
+fn state_path() -> PathBuf {
+    resolve_config_path().with_file_name(STATE_FILE_NAME)
+}
+
+fn load() -> Option<WindowGeometry> {
+    let content = fs::read_to_string(state_path()).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save(geometry: &WindowGeometry) {
+    let path = state_path();
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    match serde_json::to_string_pretty(geometry) {
+        Ok(content) => {
+            if let Err(err) = fs::write(&path, content) {
+                warn!("failed to write {}: {err}", path.display());
+            }
+        }
+        Err(err) => warn!("failed to serialize window state: {err}"),
+    }
+}
+
+/// Applies the saved geometry to `window`. Call before the window is shown.
+pub fn restore<R: Runtime>(window: &WebviewWindow<R>) {
+    let Some(geometry) = load() else {
+        return;
+    };
+    TRACKER.lock().geometry = Some(geometry);
+
+    if geometry.width > 0 && geometry.height > 0 {
+        let _ = window.set_size(PhysicalSize::new(geometry.width, geometry.height));
+        let _ = window.set_position(clamp_to_monitors(window, &geometry));
+    }
+    if geometry.maximized {
+        let _ = window.maximize();
+    }
+    if let Some(zoom) = geometry.zoom {
+        let _ = window.set_zoom(clamp_zoom(zoom));
+    }
+}
+
+fn clamp_zoom(zoom: f64) -> f64 {
+    if zoom.is_finite() {
+        zoom.clamp(MIN_ZOOM, MAX_ZOOM)
+    } else {
+        1.0
+    }
+}
+
+/// Changes the webview zoom by `step` (pass `ZOOM_STEP` or `-ZOOM_STEP`) and
+/// saves the new level.
+pub fn adjust_zoom<R: Runtime>(window: &WebviewWindow<R>, step: f64) {
+    let current = TRACKER
+        .lock()
+        .geometry
+        .and_then(|geometry| geometry.zoom)
+        .unwrap_or(1.0);
+    // Round so repeated steps don't drift to 1.0000000000000002.
+    set_zoom(window, ((current + step) * 100.0).round() / 100.0);
+}
+
+/// Returns the webview to its actual size.
+pub fn reset_zoom<R: Runtime>(window: &WebviewWindow<R>) {
+    set_zoom(window, 1.0);
+}
+
+fn set_zoom<R: Runtime>(window: &WebviewWindow<R>, zoom: f64) {
+    let zoom = clamp_zoom(zoom);
+    if let Err(err) = window.set_zoom(zoom) {
+        warn!("failed to set zoom to {zoom}: {err}");
+        return;
+    }
+    let mut tracker = TRACKER.lock();
+    let mut geometry = tracker.geometry.unwrap_or_default();
+    geometry.zoom = (zoom != 1.0).then_some(zoom);
+    tracker.geometry = Some(geometry);
+    save(&geometry);
+    tracker.last_saved = Some(Instant::now());
+}
+
+/// Keeps the saved position on a monitor that is still attached, moving it
+/// onto the nearest one otherwise.
+fn clamp_to_monitors<R: Runtime>(
+    window: &WebviewWindow<R>,
+    geometry: &WindowGeometry,
+) -> PhysicalPosition<i32> {
+    let saved = PhysicalPosition::new(geometry.x, geometry.y);
+    let monitors = window.available_monitors().unwrap_or_default();
+    if monitors.is_empty() {
+        return saved;
+    }
+
+    // Measure from the middle of the title bar, the part users need to grab.
+    let anchor_x = geometry.x as i64 + geometry.width as i64 / 2;
+    let anchor_y = geometry.y as i64 + 16;
+    let distance = |monitor: &tauri::Monitor| {
+        let pos = monitor.position();
+        let size = monitor.size();
+        let (left, top) = (pos.x as i64, pos.y as i64);
+        let (right, bottom) = (left + size.width as i64, top + size.height as i64);
+        let dx = (left - anchor_x).max(anchor_x - right).max(0);
+        let dy = (top - anchor_y).max(anchor_y - bottom).max(0);
+        dx * dx + dy * dy
+    };
+
+    let Some(nearest) = monitors.iter().min_by_key(|monitor| distance(monitor)) else {
+        return saved;
+    };
+    if distance(nearest) == 0 {
+        return saved;
+    }
+
+    let pos = nearest.position();
+    let size = nearest.size();
+    let max_x = pos.x + size.width.saturating_sub(geometry.width) as i32;
+    let max_y = pos.y + size.height.saturating_sub(geometry.height) as i32;
+    info!("saved window position is off-screen, moving onto nearest monitor");
+    PhysicalPosition::new(
+        geometry.x.clamp(pos.x, max_x),
+        geometry.y.clamp(pos.y, max_y),
+    )
+}
+
+/// Records geometry changes of the main window from `on_window_event`.
+pub fn track<R: Runtime>(window: &Window<R>, event: &WindowEvent) {
+    if window.label() != "main" {
+        return;
+    }
+    match event {
+        WindowEvent::Moved(_) | WindowEvent::Resized(_) => capture(window, false),
+        WindowEvent::CloseRequested { .. } => capture(window, true),
+        WindowEvent::Destroyed => flush(),
+        _ => {}
+    }
+}
+
+fn capture<R: Runtime>(window: &Window<R>, force: bool) {
+    // Minimized windows report meaningless positions.
+    if window.is_minimized().unwrap_or(false) {
+        return;
+    }
+    let maximized = window.is_maximized().unwrap_or(false);
+
+    let mut tracker = TRACKER.lock();
+    let mut geometry = tracker.geometry.unwrap_or_default();
+    geometry.maximized = maximized;
+    // Keep the last restored-size geometry while maximized so un-maximizing
+    // after the next launch lands somewhere sensible.
+    if !maximized {
+        if let (Ok(position), Ok(size)) = (window.outer_position(), window.inner_size()) {
+            geometry.x = position.x;
+            geometry.y = position.y;
+            geometry.width = size.width;
+            geometry.height = size.height;
+        }
+    }
+    tracker.geometry = Some(geometry);
+
+    let due = tracker
+        .last_saved
+        .is_none_or(|saved| saved.elapsed() >= SAVE_THROTTLE);
+    if force || due {
+        save(&geometry);
+        tracker.last_saved = Some(Instant::now());
+    }
+}
+
+/// Writes the most recently captured geometry, bypassing the throttle.
+pub fn flush() {
+    let mut tracker = TRACKER.lock();
+    if let Some(geometry) = tracker.geometry {
+        save(&geometry);
+        tracker.last_saved = Some(Instant::now());
+    }
+}